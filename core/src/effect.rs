@@ -0,0 +1,5 @@
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum TimedEffect {
+    Suppression{turns: i32},
+    Pinned{turns: i32},
+}
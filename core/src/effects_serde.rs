@@ -0,0 +1,50 @@
+//! Serializes `HashMap<UnitId, Vec<TimedEffect>>` as a sequence of pairs,
+//! since `UnitId` isn't a string and several serde formats (including
+//! `serde_json`'s default map support) only accept string map keys.
+
+use std::collections::HashMap;
+use std::fmt;
+use serde::ser::SerializeSeq;
+use serde::de::{Visitor, SeqAccess};
+use serde::{Serializer, Deserializer};
+use unit::UnitId;
+use effect::TimedEffect;
+
+pub fn serialize<S>(
+    map: &HashMap<UnitId, Vec<TimedEffect>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+    where S: Serializer
+{
+    let mut seq = serializer.serialize_seq(Some(map.len()))?;
+    for pair in map {
+        seq.serialize_element(&pair)?;
+    }
+    seq.end()
+}
+
+struct EffectsVisitor;
+
+impl<'de> Visitor<'de> for EffectsVisitor {
+    type Value = HashMap<UnitId, Vec<TimedEffect>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of (UnitId, Vec<TimedEffect>) pairs")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where A: SeqAccess<'de>
+    {
+        let mut map = HashMap::new();
+        while let Some((unit_id, effects)) = seq.next_element()? {
+            map.insert(unit_id, effects);
+        }
+        Ok(map)
+    }
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<UnitId, Vec<TimedEffect>>, D::Error>
+    where D: Deserializer<'de>
+{
+    deserializer.deserialize_seq(EffectsVisitor)
+}
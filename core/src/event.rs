@@ -1,4 +1,4 @@
-use std::collections::{HashMap};
+use std::collections::HashMap;
 use unit::{Unit, UnitId, UnitTypeId};
 use position::{ExactPos, MapPos};
 use player::{PlayerId};
@@ -6,6 +6,7 @@ use sector::{SectorId};
 use object::{ObjectId};
 use effect::{TimedEffect};
 use movement::{MovePoints};
+use effects_serde;
 
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum FireMode {
@@ -25,7 +26,7 @@ pub enum MoveMode {
     Hunt,
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub enum Command {
     Move{unit_id: UnitId, path: Vec<ExactPos>, mode: MoveMode},
     EndTurn,
@@ -39,7 +40,7 @@ pub enum Command {
     Smoke{unit_id: UnitId, pos: MapPos},
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AttackInfo {
     // эти поля останутся тут, потому что описывают атаку со стороны атакующего
     pub attacker_id: Option<UnitId>,
@@ -67,17 +68,22 @@ pub struct AttackInfo {
     // pub remove_move_points: bool, // TODO: заменить на Effect::Pinned
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CoreEvent {
     // TODO: точно оба поля долджны быть публичными?
     pub event: Event,
 
     // список целей и примененные к ним эффекты
     // (урон в том числе)
+    //
+    // serialized as a sequence of pairs via `effects_serde`: `UnitId` isn't
+    // a string, and not every serde format can serialize a map with
+    // non-string keys.
+    #[serde(with = "effects_serde")]
     pub effects: HashMap<UnitId, Vec<TimedEffect>>, // TODO: UnitId -> ObjectId
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Event {
     Move {
         unit_id: UnitId,
@@ -142,13 +148,19 @@ pub enum Event {
         pos: MapPos,
         count: i32,
     },
-    // TODO: CreateObject
+    CreateObject {
+        id: ObjectId,
+        pos: MapPos,
+        is_blocking: bool,
+    },
     Smoke {
         id: ObjectId,
         pos: MapPos,
         unit_id: Option<UnitId>,
     },
-    // TODO: RemoveObject
+    RemoveObject {
+        id: ObjectId,
+    },
     RemoveSmoke {
         id: ObjectId,
     },
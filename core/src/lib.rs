@@ -0,0 +1,18 @@
+// See LICENSE file for copyright and license details.
+
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+extern crate serde_json;
+
+pub mod position;
+pub mod player;
+pub mod sector;
+pub mod object;
+pub mod unit;
+pub mod effect;
+pub mod movement;
+pub mod event;
+mod effects_serde;
+pub mod spatial;
+pub mod replay;
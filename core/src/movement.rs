@@ -0,0 +1,4 @@
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MovePoints {
+    pub n: i32,
+}
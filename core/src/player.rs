@@ -0,0 +1,2 @@
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct PlayerId(pub i32);
@@ -0,0 +1,19 @@
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct MapPos {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// A hex plus a sub-tile slot: several units can share a hex (e.g. a
+/// building), so a unit's exact position needs more than just `MapPos`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct ExactPos {
+    pub map_pos: MapPos,
+    pub slot_id: i32,
+}
+
+impl ExactPos {
+    pub fn map_pos(&self) -> MapPos {
+        self.map_pos
+    }
+}
@@ -0,0 +1,42 @@
+use std::io::{Read, Write};
+use serde_json;
+use event::{CoreEvent};
+
+/// An ordered log of every `CoreEvent` emitted during a game, replayable
+/// through the normal event-application path to reconstruct identical
+/// state deterministically. This is the on-disk format for both autosave
+/// and sending a finished game over the wire.
+pub struct Replay {
+    events: Vec<CoreEvent>,
+}
+
+impl Replay {
+    pub fn new() -> Replay {
+        Replay{events: Vec::new()}
+    }
+
+    pub fn push(&mut self, event: CoreEvent) {
+        self.events.push(event);
+    }
+
+    pub fn events(&self) -> &[CoreEvent] {
+        self.events.as_slice()
+    }
+}
+
+pub fn record_to<W: Write>(replay: &Replay, writer: &mut W) -> Result<(), String> {
+    let encoded = try!(serde_json::to_string(&replay.events)
+        .map_err(|e| format!("can't encode replay: {}", e)));
+    try!(writer.write_all(encoded.as_bytes())
+        .map_err(|e| format!("can't write replay: {}", e)));
+    Ok(())
+}
+
+pub fn load_replay<R: Read>(reader: &mut R) -> Result<Replay, String> {
+    let mut contents = String::new();
+    try!(reader.read_to_string(&mut contents)
+        .map_err(|e| format!("can't read replay: {}", e)));
+    let events = try!(serde_json::from_str(&contents)
+        .map_err(|e| format!("can't decode replay: {}", e)));
+    Ok(Replay{events: events})
+}
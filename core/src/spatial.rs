@@ -0,0 +1,171 @@
+use std::collections::{HashMap, HashSet};
+use position::{MapPos};
+use unit::{Unit, UnitId};
+use object::{ObjectId};
+use event::{Event};
+
+/// Keeps track of "what's on a tile" so callers like `Pathfinder` don't
+/// have to rescan unit/object collections on every query. Patched
+/// incrementally as events are applied instead of being rebuilt from
+/// scratch each time.
+pub struct SpatialIndex {
+    units: HashMap<MapPos, Vec<UnitId>>,
+    // Every hex a unit currently occupies, anchor plus footprint — not just
+    // the anchor — so multi-hex units can be found and removed from each
+    // of their cells.
+    unit_positions: HashMap<UnitId, Vec<MapPos>>,
+    // Cached alongside `unit_positions` because `Event::Move` only carries
+    // a unit's anchor hex; re-deriving the occupied cells on a move needs
+    // the footprint that was last indexed for this unit.
+    unit_footprints: HashMap<UnitId, Vec<(i32, i32, i32)>>,
+    // Per-tile sets of blocking object ids rather than a bare `HashSet<MapPos>`: two
+    // blocking objects can share a tile (e.g. wreck + building rubble),
+    // and removing one of them must not clear the tile's blocked state
+    // while the other is still there.
+    blockers: HashMap<MapPos, HashSet<ObjectId>>,
+    blocking_objects: HashMap<ObjectId, MapPos>,
+}
+
+impl SpatialIndex {
+    pub fn new() -> SpatialIndex {
+        SpatialIndex {
+            units: HashMap::new(),
+            unit_positions: HashMap::new(),
+            unit_footprints: HashMap::new(),
+            blockers: HashMap::new(),
+            blocking_objects: HashMap::new(),
+        }
+    }
+
+    pub fn for_each_tile_content<F: FnMut(&UnitId)>(&self, pos: &MapPos, mut f: F) {
+        if let Some(unit_ids) = self.units.get(pos) {
+            for unit_id in unit_ids.iter() {
+                f(unit_id);
+            }
+        }
+    }
+
+    pub fn units_at(&self, pos: &MapPos) -> &[UnitId] {
+        match self.units.get(pos) {
+            Some(unit_ids) => unit_ids.as_slice(),
+            None => &[],
+        }
+    }
+
+    pub fn is_blocked(&self, pos: &MapPos) -> bool {
+        match self.blockers.get(pos) {
+            Some(ids) => !ids.is_empty(),
+            None => false,
+        }
+    }
+
+    pub fn add_object(&mut self, id: ObjectId, pos: &MapPos, is_blocking: bool) {
+        if is_blocking {
+            self.blockers.entry(pos.clone()).or_insert_with(HashSet::new).insert(id);
+            self.blocking_objects.insert(id, pos.clone());
+        }
+    }
+
+    pub fn remove_object(&mut self, id: ObjectId) {
+        if let Some(pos) = self.blocking_objects.remove(&id) {
+            if let Some(ids) = self.blockers.get_mut(&pos) {
+                ids.remove(&id);
+            }
+        }
+    }
+
+    fn add_unit_cell(&mut self, pos: &MapPos, unit_id: UnitId) {
+        self.units.entry(pos.clone()).or_insert_with(Vec::new).push(unit_id);
+    }
+
+    fn remove_unit_cell(&mut self, pos: &MapPos, unit_id: UnitId) {
+        if let Some(unit_ids) = self.units.get_mut(pos) {
+            unit_ids.retain(|id| *id != unit_id);
+        }
+    }
+
+    /// Indexes a unit at every hex of its footprint, anchored at `anchor`.
+    fn add_unit_at(&mut self, anchor: &MapPos, unit_id: UnitId, footprint: Vec<(i32, i32, i32)>) {
+        let cells = footprint_cells(anchor, &footprint);
+        for cell in &cells {
+            self.add_unit_cell(cell, unit_id);
+        }
+        self.unit_positions.insert(unit_id, cells);
+        self.unit_footprints.insert(unit_id, footprint);
+    }
+
+    /// Removes a unit from every cell it occupies, without already knowing
+    /// its tiles, by looking them up in `unit_positions` instead of
+    /// scanning every tile's contents.
+    fn remove_unit_by_id(&mut self, unit_id: UnitId) {
+        if let Some(cells) = self.unit_positions.remove(&unit_id) {
+            for cell in &cells {
+                self.remove_unit_cell(cell, unit_id);
+            }
+        }
+        self.unit_footprints.remove(&unit_id);
+    }
+
+    fn add_unit_info(&mut self, unit_info: &Unit) {
+        self.add_unit_at(
+            &unit_info.pos.map_pos(), unit_info.id, unit_info.footprint().to_vec());
+    }
+
+    /// Patches the index in place for an applied event, keeping it in
+    /// sync with `GameState` without rescanning every unit.
+    pub fn apply_event(&mut self, event: &Event) {
+        match *event {
+            Event::Move{unit_id, ref to, ..} => {
+                let footprint = self.unit_footprints.get(&unit_id).cloned().unwrap_or_default();
+                self.remove_unit_by_id(unit_id);
+                self.add_unit_at(&to.map_pos(), unit_id, footprint);
+            },
+            Event::CreateUnit{ref unit_info} => {
+                self.add_unit_info(unit_info);
+            },
+            Event::HideUnit{unit_id} => {
+                self.remove_unit_by_id(unit_id);
+            },
+            Event::ShowUnit{ref unit_info} => {
+                self.add_unit_info(unit_info);
+            },
+            Event::CreateObject{id, ref pos, is_blocking} => {
+                self.add_object(id, pos, is_blocking);
+            },
+            Event::RemoveObject{id} => {
+                self.remove_object(id);
+            },
+            // Smoke never blocks movement or occupies a tile slot, so it's
+            // intentionally not indexed here; `Event::Smoke`/`RemoveSmoke`
+            // only affect line-of-sight, which this index doesn't track.
+            Event::Smoke{..} | Event::RemoveSmoke{..} => {},
+            _ => {},
+        }
+    }
+}
+
+/// Offset positions of every hex a footprint-bearing unit occupies:
+/// `anchor` itself plus `anchor + offset` for each cube-coordinate offset
+/// in `footprint`.
+fn footprint_cells(anchor: &MapPos, footprint: &[(i32, i32, i32)]) -> Vec<MapPos> {
+    let (ax, ay, az) = to_cube(anchor);
+    let mut cells = vec![anchor.clone()];
+    for &(dx, dy, dz) in footprint {
+        cells.push(from_cube(ax + dx, ay + dy, az + dz));
+    }
+    cells
+}
+
+fn to_cube(pos: &MapPos) -> (i32, i32, i32) {
+    let x = pos.x;
+    let z = pos.y - (pos.x - (pos.x & 1)) / 2;
+    let y = -x - z;
+    (x, y, z)
+}
+
+/// Inverse of `to_cube`.
+fn from_cube(x: i32, _y: i32, z: i32) -> MapPos {
+    let col = x;
+    let row = z + (x - (x & 1)) / 2;
+    MapPos { x: col, y: row }
+}
@@ -0,0 +1,24 @@
+use position::{ExactPos};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct UnitId(pub i32);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct UnitTypeId(pub i32);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Unit {
+    pub id: UnitId,
+    pub type_id: UnitTypeId,
+    pub pos: ExactPos,
+
+    /// Extra hexes this unit occupies, as cube-coordinate offsets from
+    /// `pos` (see chunk0-5). Empty for ordinary single-hex units.
+    pub footprint: Vec<(i32, i32, i32)>,
+}
+
+impl Unit {
+    pub fn footprint(&self) -> &[(i32, i32, i32)] {
+        &self.footprint
+    }
+}
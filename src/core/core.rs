@@ -0,0 +1,57 @@
+// See LICENSE file for copyright and license details.
+
+use core::types::{ZInt, MapPos};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnitClass {
+    Infantry,
+    Vehicle,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UnitTypeId(pub ZInt);
+
+pub struct UnitType {
+    pub class: UnitClass,
+}
+
+pub struct ObjectTypes {
+    unit_types: Vec<UnitType>,
+}
+
+impl ObjectTypes {
+    pub fn new(unit_types: Vec<UnitType>) -> ObjectTypes {
+        ObjectTypes{unit_types: unit_types}
+    }
+
+    pub fn get_unit_type(&self, type_id: &UnitTypeId) -> &UnitType {
+        &self.unit_types[type_id.0 as usize]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UnitId(pub ZInt);
+
+#[derive(Clone, Debug)]
+pub struct Unit {
+    pub id: UnitId,
+    pub type_id: UnitTypeId,
+    pub pos: MapPos,
+    pub move_points: ZInt,
+
+    /// Extra hexes this unit occupies, as cube-coordinate offsets from
+    /// `pos` (see chunk0-5). Empty for ordinary single-hex units.
+    pub footprint: Vec<(ZInt, ZInt, ZInt)>,
+}
+
+impl Unit {
+    pub fn footprint(&self) -> &[(ZInt, ZInt, ZInt)] {
+        &self.footprint
+    }
+}
+
+pub struct Core {
+    pub object_types: ObjectTypes,
+}
+
+// vim: set tabstop=4 shiftwidth=4 softtabstop=4 expandtab:
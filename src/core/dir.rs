@@ -0,0 +1,69 @@
+// See LICENSE file for copyright and license details.
+
+use cgmath::Vector2;
+use core::types::{ZInt, MapPos};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Dir {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+// Neighbour (col, row) deltas for the "odd-q" vertical hex layout: which
+// delta a direction maps to depends on whether the source column is even
+// or odd, so the table is indexed by `[col & 1][dir]`.
+static NEIGHBOUR_OFFSETS: [[(ZInt, ZInt); 6]; 2] = [
+    // even columns
+    [(1, 0), (1, -1), (0, -1), (-1, -1), (-1, 0), (0, 1)],
+    // odd columns
+    [(1, 1), (1, 0), (0, -1), (-1, 0), (-1, 1), (0, 1)],
+];
+
+impl Dir {
+    pub fn from_int(n: ZInt) -> Dir {
+        match n {
+            0 => Dir::East,
+            1 => Dir::NorthEast,
+            2 => Dir::NorthWest,
+            3 => Dir::West,
+            4 => Dir::SouthWest,
+            5 => Dir::SouthEast,
+            _ => panic!("bad dir index: {}", n),
+        }
+    }
+
+    pub fn to_int(&self) -> ZInt {
+        match *self {
+            Dir::East => 0,
+            Dir::NorthEast => 1,
+            Dir::NorthWest => 2,
+            Dir::West => 3,
+            Dir::SouthWest => 4,
+            Dir::SouthEast => 5,
+        }
+    }
+
+    pub fn get_neighbour_pos(pos: &MapPos, dir: &Dir) -> MapPos {
+        let parity = (pos.v.x & 1) as usize;
+        let (dx, dy) = NEIGHBOUR_OFFSETS[parity][dir.to_int() as usize];
+        MapPos{v: Vector2{x: pos.v.x + dx, y: pos.v.y + dy}}
+    }
+
+    /// Finds which direction leads from `from` to its neighbour `to`, by
+    /// brute-force search over all six directions.
+    pub fn get_dir_from_to(from: &MapPos, to: &MapPos) -> Dir {
+        for i in range(0, 6) {
+            let dir = Dir::from_int(i as ZInt);
+            if Dir::get_neighbour_pos(from, &dir) == *to {
+                return dir;
+            }
+        }
+        panic!("{:?} is not a neighbour of {:?}", to, from)
+    }
+}
+
+// vim: set tabstop=4 shiftwidth=4 softtabstop=4 expandtab:
@@ -0,0 +1,66 @@
+// See LICENSE file for copyright and license details.
+
+use std::collections::{HashMap, HashSet};
+use core::types::{MapPos};
+use core::core::{UnitId};
+use core::map::{Map};
+use core::pathfinder::{Pathfinder, MapPath, Error};
+
+/// Tracks unit occupancy and blocked tiles for the local pathfinder, kept
+/// separate from `core::core::Core`/`Unit` storage since those are owned
+/// by the caller (e.g. the visualizer). It has no methods that add units
+/// or blocked tiles, so an index made by `new` stays empty.
+pub struct LocalSpatialIndex {
+    units: HashMap<MapPos, Vec<UnitId>>,
+    blocked: HashSet<MapPos>,
+}
+
+impl LocalSpatialIndex {
+    pub fn new() -> LocalSpatialIndex {
+        LocalSpatialIndex {
+            units: HashMap::new(),
+            blocked: HashSet::new(),
+        }
+    }
+
+    pub fn units_at(&self, pos: &MapPos) -> &[UnitId] {
+        match self.units.get(pos) {
+            Some(ids) => ids.as_slice(),
+            None => &[],
+        }
+    }
+
+    pub fn is_blocked(&self, pos: &MapPos) -> bool {
+        self.blocked.contains(pos)
+    }
+}
+
+pub struct GameState {
+    pub map: Map,
+    spatial: LocalSpatialIndex,
+}
+
+impl GameState {
+    pub fn new(map: Map) -> GameState {
+        GameState {
+            map: map,
+            spatial: LocalSpatialIndex::new(),
+        }
+    }
+
+    pub fn spatial(&self) -> &LocalSpatialIndex {
+        &self.spatial
+    }
+
+    /// The move-command handler: re-validates `path` against the current
+    /// map state before a `Command::Move` is allowed to execute, so a tile
+    /// that became blocked after the path was computed (e.g. rubble from
+    /// a destroyed object) actually stops the move instead of silently
+    /// being walked through. Callers only update the unit's position once
+    /// this returns `Ok`.
+    pub fn execute_move(&self, pathfinder: &Pathfinder, path: &MapPath) -> Result<(), Error> {
+        pathfinder.validate_path(self, path)
+    }
+}
+
+// vim: set tabstop=4 shiftwidth=4 softtabstop=4 expandtab:
@@ -0,0 +1,36 @@
+// See LICENSE file for copyright and license details.
+
+use core::types::{ZInt, MapPos, Size2};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tile {
+    Plain,
+    Trees,
+    Building,
+    Water,
+}
+
+pub struct Map {
+    size: Size2<ZInt>,
+    tiles: Vec<Tile>,
+}
+
+impl Map {
+    pub fn new(size: Size2<ZInt>, tiles: Vec<Tile>) -> Map {
+        assert_eq!(tiles.len() as ZInt, size.w * size.h);
+        Map {
+            size: size,
+            tiles: tiles,
+        }
+    }
+
+    pub fn tile(&self, pos: &MapPos) -> &Tile {
+        &self.tiles[(pos.v.x + pos.v.y * self.size.w) as usize]
+    }
+
+    pub fn get_size(&self) -> &Size2<ZInt> {
+        &self.size
+    }
+}
+
+// vim: set tabstop=4 shiftwidth=4 softtabstop=4 expandtab:
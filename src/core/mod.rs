@@ -0,0 +1,10 @@
+// See LICENSE file for copyright and license details.
+
+pub mod types;
+pub mod dir;
+pub mod map;
+pub mod core;
+pub mod game_state;
+pub mod pathfinder;
+
+// vim: set tabstop=4 shiftwidth=4 softtabstop=4 expandtab:
@@ -1,11 +1,18 @@
 // See LICENSE file for copyright and license details.
 
+use std::collections::{BinaryHeap, HashMap};
+use cgmath::Vector2;
 use core::types::{ZInt, MapPos, Size2};
 use core::core::{Core, Unit, UnitClass};
 use core::map;
 use core::game_state::{GameState};
 use core::dir::{Dir};
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    TileIsBlocked,
+}
+
 #[derive(Clone)]
 pub struct MoveCost{pub n: ZInt}
 
@@ -20,6 +27,8 @@ impl MapPath {
         self.nodes.len() as ZInt
     }
 
+    /// For multi-hex units this is the footprint's anchor hex, not every
+    /// hex the unit occupies there.
     pub fn destination(&self) -> &MapPos {
         let &(_, ref pos) = self.nodes.last().unwrap();
         pos
@@ -119,15 +128,49 @@ impl Pathfinder {
                 &map::Tile::Plain => 1,
                 &map::Tile::Trees => 2,
                 &map::Tile::Building => 2,
+                &map::Tile::Water => max_cost().n,
             },
             UnitClass::Vehicle => match tile {
                 &map::Tile::Plain => 1,
                 &map::Tile::Trees => 5,
                 &map::Tile::Building => 10,
+                &map::Tile::Water => max_cost().n,
             },
         }
     }
 
+    /// The hexes a `unit` would occupy if its footprint's anchor hex was
+    /// `anchor`: just `anchor` itself for ordinary single-hex units, plus
+    /// one cell per relative cube offset in `Unit::footprint` for larger
+    /// ones (e.g. `(1, -1, 0)` and `(2, -2, 0)` for a 3-hex train in a
+    /// straight line — offsets aren't limited to immediate neighbours).
+    fn footprint_cells(&self, unit: &Unit, anchor: &MapPos) -> Vec<MapPos> {
+        let (ax, ay, az) = to_cube(anchor);
+        let mut cells = vec![anchor.clone()];
+        for &(dx, dy, dz) in unit.footprint() {
+            cells.push(from_cube(ax + dx, ay + dy, az + dz));
+        }
+        cells
+    }
+
+    fn footprint_is_clear(&self, state: &GameState, unit: &Unit, anchor: &MapPos) -> bool {
+        self.footprint_cells(unit, anchor).iter().all(|pos| {
+            self.map.is_inboard(pos)
+                && !is_tile_blocked(state, pos)
+                && state.spatial().units_at(pos).iter().all(|id| *id == unit.id)
+        })
+    }
+
+    /// The cost of entering `anchor` with its whole footprint: the worst
+    /// (most expensive) tile under the unit, since every hex it covers
+    /// has to be crossed.
+    fn footprint_cost(&self, core: &Core, state: &GameState, unit: &Unit, anchor: &MapPos) -> ZInt {
+        self.footprint_cells(unit, anchor).iter()
+            .map(|pos| self.tile_cost(core, state, unit, pos))
+            .max()
+            .expect("a footprint always covers at least its anchor hex")
+    }
+
     fn process_neighbour_pos(
         &mut self,
         core: &Core,
@@ -136,13 +179,14 @@ impl Pathfinder {
         original_pos: &MapPos,
         neighbour_pos: &MapPos
     ) {
+        if !self.footprint_is_clear(state, unit, neighbour_pos) {
+            return;
+        }
         let old_cost = self.map.tile(original_pos).cost.clone();
-        let tile_cost = self.tile_cost(core, state, unit, neighbour_pos);
+        let tile_cost = self.footprint_cost(core, state, unit, neighbour_pos);
         let tile = self.map.tile_mut(neighbour_pos);
         let new_cost = MoveCost{n: old_cost.n + tile_cost};
-        let units_count = state.units_at(neighbour_pos).len();
         if tile.cost.n > new_cost.n
-            && units_count == 0
             && new_cost.n <= unit.move_points
         {
             tile.cost = new_cost;
@@ -195,18 +239,19 @@ impl Pathfinder {
     }
 
     pub fn get_path(&self, destination: &MapPos) -> MapPath {
-        let mut total_cost = MoveCost{n: 0};
         let mut path = Vec::new();
         let mut pos = destination.clone();
         assert!(self.map.is_inboard(&pos));
+        let total_cost = self.map.tile(&pos).cost.clone();
         path.push((MoveCost{n: 0}, destination.clone()));
         while self.map.tile(&pos).cost.n != 0 {
+            let cost_here = self.map.tile(&pos).cost.clone();
             let parent_dir = self.map.tile(&pos)
                 .parent().as_ref().unwrap().clone(); // TODO: ?!
             pos = Dir::get_neighbour_pos(&pos, &parent_dir);
             assert!(self.map.is_inboard(&pos));
-            let cost = MoveCost{n: 1};
-            total_cost.n += cost.n;
+            let cost_parent = self.map.tile(&pos).cost.clone();
+            let cost = MoveCost{n: cost_here.n - cost_parent.n};
             path.push((cost, pos.clone()));
         }
         path.reverse();
@@ -215,6 +260,162 @@ impl Pathfinder {
             total_cost: total_cost,
         }
     }
+
+    /// Validates that no step of an already-built `path` has become
+    /// blocked — e.g. after a boulder or rubble appeared on the map
+    /// since the path was computed. Callers run this right before
+    /// executing a `Command::Move`. Only each node's own hex is checked
+    /// against blocking objects; units standing on it and the other
+    /// hexes of a multi-hex unit's footprint are not checked.
+    pub fn validate_path(&self, state: &GameState, path: &MapPath) -> Result<(), Error> {
+        for &(_, ref pos) in path.nodes().iter() {
+            if is_tile_blocked(state, pos) {
+                return Err(Error::TileIsBlocked);
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds a route to a single `destination` without flooding the whole
+    /// map, using A* with a hex-distance heuristic.
+    pub fn get_path_to(
+        &self,
+        core: &Core,
+        state: &GameState,
+        unit: &Unit,
+        destination: &MapPos,
+    ) -> Option<MapPath> {
+        assert!(self.map.is_inboard(destination));
+        let start_pos = unit.pos.clone();
+        let mut open = BinaryHeap::new();
+        let mut g_score = HashMap::new();
+        let mut came_from: HashMap<MapPos, Option<Dir>> = HashMap::new();
+        g_score.insert(start_pos.clone(), MoveCost{n: 0});
+        came_from.insert(start_pos.clone(), None);
+        open.push(OpenEntry{
+            f: hex_distance(&start_pos, destination),
+            pos: start_pos.clone(),
+        });
+        while let Some(OpenEntry{pos, ..}) = open.pop() {
+            if pos == *destination {
+                return Some(reconstruct_astar_path(&came_from, &g_score, destination));
+            }
+            let g = g_score[&pos].clone();
+            for i in range(0, 6) {
+                let dir = Dir::from_int(i as ZInt);
+                let neighbour_pos = Dir::get_neighbour_pos(&pos, &dir);
+                if !self.map.is_inboard(&neighbour_pos) {
+                    continue;
+                }
+                if !self.footprint_is_clear(state, unit, &neighbour_pos) {
+                    continue;
+                }
+                let tile_cost = self.footprint_cost(core, state, unit, &neighbour_pos);
+                let new_g = MoveCost{n: g.n + tile_cost};
+                if new_g.n > unit.move_points {
+                    continue;
+                }
+                let is_better = match g_score.get(&neighbour_pos) {
+                    Some(old_g) => new_g.n < old_g.n,
+                    None => true,
+                };
+                if is_better {
+                    g_score.insert(neighbour_pos.clone(), new_g.clone());
+                    came_from.insert(neighbour_pos.clone(), Some(
+                        Dir::get_dir_from_to(&neighbour_pos, &pos)));
+                    let f = new_g.n + hex_distance(&neighbour_pos, destination);
+                    open.push(OpenEntry{f: f, pos: neighbour_pos});
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Checks whether `pos` is blocked by an impassable object (rubble, a
+/// boulder, ...), as opposed to merely being occupied by a unit. Such
+/// tiles are never enterable, regardless of move points.
+fn is_tile_blocked(state: &GameState, pos: &MapPos) -> bool {
+    state.spatial().is_blocked(pos)
+}
+
+/// Open-set entry for `get_path_to`'s A* search. Orders purely on `f`,
+/// reversed so that `BinaryHeap` (a max-heap) surfaces the smallest `f`
+/// first; `MapPos` itself doesn't need to be `Ord` for this.
+struct OpenEntry {
+    f: ZInt,
+    pos: MapPos,
+}
+
+impl PartialEq for OpenEntry {
+    fn eq(&self, other: &OpenEntry) -> bool {
+        self.f == other.f
+    }
+}
+
+impl Eq for OpenEntry {}
+
+impl PartialOrd for OpenEntry {
+    fn partial_cmp(&self, other: &OpenEntry) -> Option<::std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenEntry {
+    fn cmp(&self, other: &OpenEntry) -> ::std::cmp::Ordering {
+        other.f.cmp(&self.f)
+    }
+}
+
+fn reconstruct_astar_path(
+    came_from: &HashMap<MapPos, Option<Dir>>,
+    g_score: &HashMap<MapPos, MoveCost>,
+    destination: &MapPos,
+) -> MapPath {
+    let mut path = Vec::new();
+    let mut pos = destination.clone();
+    path.push((MoveCost{n: 0}, pos.clone()));
+    loop {
+        let parent_dir = match came_from[&pos] {
+            Some(ref dir) => dir.clone(),
+            None => break,
+        };
+        let cost_here = g_score[&pos].clone();
+        let parent_pos = Dir::get_neighbour_pos(&pos, &parent_dir);
+        let cost_parent = g_score[&parent_pos].clone();
+        pos = parent_pos;
+        path.push((MoveCost{n: cost_here.n - cost_parent.n}, pos.clone()));
+    }
+    path.reverse();
+    MapPath {
+        nodes: path,
+        total_cost: g_score[destination].clone(),
+    }
+}
+
+/// Converts an offset `MapPos` to cube coordinates and returns the hex
+/// distance between two positions, used as the admissible A* heuristic
+/// (every tile costs at least 1 to enter).
+fn hex_distance(a: &MapPos, b: &MapPos) -> ZInt {
+    let (ax, ay, az) = to_cube(a);
+    let (bx, by, bz) = to_cube(b);
+    ((ax - bx).abs() + (ay - by).abs() + (az - bz).abs()) / 2
+}
+
+fn to_cube(pos: &MapPos) -> (ZInt, ZInt, ZInt) {
+    let x = pos.v.x;
+    let z = pos.v.y - (pos.v.x - (pos.v.x & 1)) / 2;
+    let y = -x - z;
+    (x, y, z)
+}
+
+/// Inverse of `to_cube`: turns cube coordinates back into an offset
+/// `MapPos`, used to place footprint cells that may be more than one
+/// hex step away from the anchor.
+fn from_cube(x: ZInt, _y: ZInt, z: ZInt) -> MapPos {
+    let col = x;
+    let row = z + (x - (x & 1)) / 2;
+    MapPos{v: Vector2{x: col, y: row}}
 }
 
 // vim: set tabstop=4 shiftwidth=4 softtabstop=4 expandtab:
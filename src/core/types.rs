@@ -0,0 +1,36 @@
+// See LICENSE file for copyright and license details.
+
+use std::hash::{Hash, Hasher};
+use cgmath::Vector2;
+
+pub type ZInt = i32;
+
+#[derive(Clone, Debug)]
+pub struct Size2<T> {
+    pub w: T,
+    pub h: T,
+}
+
+#[derive(Clone, Debug)]
+pub struct MapPos {
+    pub v: Vector2<ZInt>,
+}
+
+// Manual impls: `Vector2` doesn't derive `Eq`/`Hash`, and `MapPos` needs
+// both to be usable as a `HashMap` key in the pathfinder's A* score maps.
+impl PartialEq for MapPos {
+    fn eq(&self, other: &MapPos) -> bool {
+        self.v.x == other.v.x && self.v.y == other.v.y
+    }
+}
+
+impl Eq for MapPos {}
+
+impl Hash for MapPos {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.v.x.hash(state);
+        self.v.y.hash(state);
+    }
+}
+
+// vim: set tabstop=4 shiftwidth=4 softtabstop=4 expandtab: